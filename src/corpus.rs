@@ -0,0 +1,146 @@
+use std::io::{self, Write};
+
+use scraper::{ElementRef, Html, Selector};
+
+/// A single forum post extracted from the scraped HTML: its author, its
+/// permalink, and its cleaned, whitespace-collapsed body text.
+#[derive(Debug, Clone)]
+pub struct Message {
+    pub author: String,
+    pub url: String,
+    pub body: String,
+}
+
+/// The messages extracted from a scraped HTML document, ready to be
+/// tokenized into the word stream a training pipeline expects, or used to
+/// build a vocabulary for subsetting a pretrained [`crate::parser::Word2Vec`].
+pub struct Corpus {
+    messages: Vec<Message>,
+}
+
+impl Corpus {
+    /// Extracts every `article.message` element in `html` into a [`Message`]:
+    /// its `data-author` and `itemid` attributes, and its body text with
+    /// nested markup stripped, entities decoded, and whitespace collapsed.
+    /// Elements missing either attribute are skipped.
+    pub fn from_html(html: &Html) -> Self {
+        let message_selector = Selector::parse("article.message").expect("valid selector");
+        let body_selector = Selector::parse(".message-body").expect("valid selector");
+
+        let messages = html
+            .select(&message_selector)
+            .filter_map(|elem| {
+                let author = elem.attr("data-author")?.to_string();
+                let url = elem.attr("itemid")?.to_string();
+                let body = extract_body(&elem, &body_selector);
+
+                Some(Message { author, url, body })
+            })
+            .collect();
+
+        Corpus { messages }
+    }
+
+    /// The extracted messages, in document order.
+    pub fn messages(&self) -> &[Message] {
+        &self.messages
+    }
+
+    /// Lowercased word tokens across every message, in order.
+    pub fn tokens(&self) -> impl Iterator<Item = String> + '_ {
+        self.messages.iter().flat_map(|m| m.body.split_whitespace()).map(str::to_lowercase)
+    }
+
+    /// Writes one tokenized sentence per line: each message's lowercased
+    /// word tokens, space-separated. This is the format a training pipeline
+    /// reads, and the vocabulary it implies can be used to filter which
+    /// embeddings to keep when subsetting a pretrained model.
+    pub fn write_tokens<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        for message in &self.messages {
+            let sentence: Vec<String> = message.body.split_whitespace().map(str::to_lowercase).collect();
+            writeln!(w, "{}", sentence.join(" "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Pulls an element's text content, stripping any nested markup (`.text()`
+/// only yields text nodes) and collapsing runs of whitespace into single
+/// spaces. Entity decoding is handled by `scraper`/`html5ever` while
+/// parsing, so by the time we see it the text is already plain UTF-8. Falls
+/// back to the whole message's text if `body_selector` doesn't match.
+fn extract_body(message: &ElementRef, body_selector: &Selector) -> String {
+    let raw: String = match message.select(body_selector).next() {
+        Some(body) => body.text().collect::<Vec<_>>().join(" "),
+        None => message.text().collect::<Vec<_>>().join(" "),
+    };
+
+    raw.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn html_fixture() -> Html {
+        Html::parse_document(
+            r#"<html><body>
+                <article class="message" data-author="alice" itemid="https://forum.example/posts/1">
+                    <div class="message-body">  Hello   <b>World</b>!  </div>
+                </article>
+                <article class="message" data-author="bob" itemid="https://forum.example/posts/2">
+                    <div class="message-body">Another Message</div>
+                </article>
+                <article class="message">
+                    <div class="message-body">Missing attributes, should be skipped</div>
+                </article>
+            </body></html>"#,
+        )
+    }
+
+    #[test]
+    fn test_from_html_extracts_messages() {
+        let corpus = Corpus::from_html(&html_fixture());
+
+        assert_eq!(corpus.messages().len(), 2);
+
+        let first = &corpus.messages()[0];
+        assert_eq!(first.author, "alice");
+        assert_eq!(first.url, "https://forum.example/posts/1");
+        assert_eq!(first.body, "Hello World !");
+    }
+
+    #[test]
+    fn test_from_html_skips_messages_missing_attributes() {
+        let corpus = Corpus::from_html(&html_fixture());
+
+        assert!(corpus.messages().iter().all(|m| !m.author.is_empty()));
+    }
+
+    #[test]
+    fn test_tokens_are_lowercased() {
+        let corpus = Corpus::from_html(&html_fixture());
+
+        let tokens: Vec<String> = corpus.tokens().collect();
+
+        assert!(tokens.contains(&"hello".to_string()));
+        assert!(tokens.contains(&"world".to_string()));
+        assert!(!tokens.iter().any(|t| t.chars().any(char::is_uppercase)));
+    }
+
+    #[test]
+    fn test_write_tokens_emits_one_sentence_per_line() {
+        let corpus = Corpus::from_html(&html_fixture());
+
+        let mut buf = Vec::new();
+        corpus.write_tokens(&mut buf).unwrap();
+
+        let output = String::from_utf8(buf).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "hello world !");
+        assert_eq!(lines[1], "another message");
+    }
+}
@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+/// Errors that can occur while parsing or writing a word2vec embedding file.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("malformed header: expected `<count> <dim>\\n`")]
+    BadHeader,
+
+    #[error("unexpected end of input")]
+    UnexpectedEof,
+
+    #[error("{remaining} trailing byte(s) after the last embedding")]
+    TrailingBytes { remaining: usize },
+
+    #[error("word at offset {offset:#x} is not valid UTF-8")]
+    InvalidUtf8Word { offset: usize },
+
+    #[error("dimension mismatch: expected {expected}, got {got}")]
+    DimensionMismatch { expected: usize, got: usize },
+
+    #[error("invalid floating point value at offset {offset:#x}")]
+    InvalidFloat { offset: usize },
+
+    #[error("line at offset {offset:#x} has no word token")]
+    MissingWord { offset: usize },
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
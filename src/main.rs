@@ -1,17 +1,19 @@
-use scraper::{Html, Selector};
+use std::{fs::File, io::BufWriter};
+
+use fckng_w2v::corpus::Corpus;
+use scraper::Html;
 
 fn main() -> anyhow::Result<()> {
     let html = include_str!("../page.html");
-    let html = Html::parse_document(&html);
+    let html = Html::parse_document(html);
+
+    let corpus = Corpus::from_html(&html);
 
-    let s = Selector::parse("article.message").unwrap();
+    let output = File::create("corpus.txt")?;
+    let mut writer = BufWriter::new(output);
+    corpus.write_tokens(&mut writer)?;
 
-    let messages = html.select(&s);
-    for elem in messages {
-        let author = elem.attr("data-author").unwrap();
-        let message_url = elem.attr("itemid").unwrap();
-        println!("{:#?}", message_url);
-    }
+    println!("wrote {} sentence(s) to corpus.txt", corpus.messages().len());
 
     Ok(())
 }
@@ -1,6 +1,14 @@
 use core::str;
-use std::{collections::HashMap, fs::File, path::Path};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::Path,
+};
 
+use byteorder::{LittleEndian, WriteBytesExt};
+use flate2::bufread::{GzDecoder, ZlibDecoder};
 use memmap2::MmapOptions;
 use nom::{
     bytes::complete::{tag, take, take_till1},
@@ -10,6 +18,8 @@ use nom::{
     IResult,
 };
 
+use crate::error::Error;
+
 /// Parses an ASCII-encoded `u32` value from a byte slice, terminated by a specified ASCII character.
 /// Fails if the number does not fit in an u32, if it is not terminated, or if
 /// it is terminated by a different character.
@@ -21,7 +31,13 @@ fn ascii_u32_terminated_by(input: &[u8], terminator: u8) -> IResult<&[u8], u32>
     let (input, _) = tag(&[terminator])(input)?;
 
     let n_str = str::from_utf8(n).unwrap(); // will always be a valid UTF-8 str as digit1 did not return an Err
-    let n = n_str.parse::<u32>().unwrap(); // will always be a valid digit as digit1 did not return an Err
+
+    // digit1 accepts an arbitrarily long run of digits, so this can still
+    // fail if the number doesn't fit in a u32 — surface that as a nom
+    // failure instead of panicking.
+    let n = n_str
+        .parse::<u32>()
+        .map_err(|_| nom::Err::Failure(nom::error::Error::new(n, nom::error::ErrorKind::TooLarge)))?;
 
     Ok((input, n))
 }
@@ -33,12 +49,12 @@ pub(crate) struct Word2VecHeader {
 }
 
 impl Word2VecHeader {
-    pub(crate) fn parse(input: &[u8]) -> IResult<&[u8], Self> {
+    pub(crate) fn parse(input: &[u8]) -> Result<(&[u8], Self), Error> {
         // The header is encoded like this:
         // <ASCII embeddings_count><SPACE><ASCII embeddings_dim><LF>
 
-        let (bytes, embeddings_count) = ascii_u32_terminated_by(input, b' ').unwrap();
-        let (bytes, embeddings_dim) = ascii_u32_terminated_by(bytes, 0x0A).unwrap(); // 0x0A is a Line Feed
+        let (bytes, embeddings_count) = ascii_u32_terminated_by(input, b' ').map_err(|_| Error::BadHeader)?;
+        let (bytes, embeddings_dim) = ascii_u32_terminated_by(bytes, 0x0A).map_err(|_| Error::BadHeader)?; // 0x0A is a Line Feed
 
         let header = Word2VecHeader {
             embeddings_count,
@@ -53,82 +69,648 @@ impl Word2VecHeader {
 pub(crate) struct Word2VecEmbedding {
     pub(crate) word: String, // TODO: switch from String to &str using the lifetime of the mmap
     pub(crate) embedding: Vec<f32>,
+    /// Euclidean norm of `embedding`, cached at parse time so similarity
+    /// queries don't have to recompute it on every lookup.
+    pub(crate) norm: f32,
+}
+
+fn vector_norm(v: &[f32]) -> f32 {
+    v.iter().map(|x| x * x).sum::<f32>().sqrt()
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
 }
 
-fn string_terminated_by(input: &[u8], terminator: u8) -> IResult<&[u8], String> {
+fn bytes_terminated_by(input: &[u8], terminator: u8) -> IResult<&[u8], &[u8]> {
     // Take everything till terminator
     let (input, s) = take_till1(|c| c == terminator)(input)?;
 
     // Consume the terminator
     let (input, _) = tag(&[terminator])(input)?;
 
-    // Turn the bytes into a String
-    let s = String::from_utf8_lossy(s).to_string();
-
     Ok((input, s))
 }
 
 impl Word2VecEmbedding {
-    pub(crate) fn parse(input: &[u8], embeddings_dim: u32) -> IResult<&[u8], Self> {
+    /// Parses a single embedding entry starting at `input`. `offset` is this
+    /// entry's absolute byte offset in the source file, used to report where
+    /// a malformed word was found.
+    pub(crate) fn parse(input: &[u8], embeddings_dim: u32, offset: usize) -> Result<(&[u8], Self), Error> {
         // Each embedding is encoded like this:
         // <ASCII word><SPACE><N adjacent 32-bit floats with little endian ordering>
 
-        let (bytes, word) = string_terminated_by(input, b' ').unwrap();
+        let (bytes, word) = bytes_terminated_by(input, b' ').map_err(|_| Error::UnexpectedEof)?;
+        let word = str::from_utf8(word)
+            .map_err(|_| Error::InvalidUtf8Word { offset })?
+            .to_string();
 
         // we have f32_len * embeddings_dim bytes that represents our embeddings
-        let (bytes, embedding) = take(embeddings_dim as usize * std::mem::size_of::<f32>())(bytes)?;
+        let (bytes, embedding) =
+            take(embeddings_dim as usize * std::mem::size_of::<f32>())(bytes).map_err(|_: nom::Err<nom::error::Error<&[u8]>>| Error::UnexpectedEof)?;
 
         // dimensions are stored next to each other as 32-bit floats with little endian ordering
-        let (remaning, embedding) = count(le_f32, 300usize)(embedding)?;
+        let (remaining, embedding) =
+            count(le_f32, embeddings_dim as usize)(embedding).map_err(|_: nom::Err<nom::error::Error<&[u8]>>| Error::UnexpectedEof)?;
+
+        assert_eq!(remaining.len(), 0); // we should be at the end of what we've taken, take() guarantees this
 
-        assert_eq!(remaning.len(), 0); // we should be at the end of what we've taken
+        let norm = vector_norm(&embedding);
 
-        Ok((bytes, Word2VecEmbedding { word, embedding }))
+        Ok((bytes, Word2VecEmbedding { word, embedding, norm }))
     }
 }
 
-pub(crate) struct Word2Vec {
+/// A single scored candidate in a top-k similarity search, ordered by `score`
+/// so it can be kept in a bounded `BinaryHeap`.
+struct ScoredWord<'a> {
+    score: f32,
+    word: &'a str,
+}
+
+impl PartialEq for ScoredWord<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.score == other.score
+    }
+}
+
+impl Eq for ScoredWord<'_> {}
+
+impl PartialOrd for ScoredWord<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScoredWord<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.score.total_cmp(&other.score)
+    }
+}
+
+/// Keeps the `k` highest-scoring candidates out of `candidates`, using a
+/// bounded min-heap so memory stays `O(k)` regardless of how many candidates
+/// are scanned. Returns them sorted from most to least similar.
+fn top_k<'a>(candidates: impl Iterator<Item = (&'a str, f32)>, k: usize) -> Vec<(&'a str, f32)> {
+    let mut heap: BinaryHeap<Reverse<ScoredWord<'a>>> = BinaryHeap::with_capacity(k);
+
+    for (word, score) in candidates {
+        if heap.len() < k {
+            heap.push(Reverse(ScoredWord { score, word }));
+        } else if let Some(Reverse(min)) = heap.peek() {
+            if score > min.score {
+                heap.pop();
+                heap.push(Reverse(ScoredWord { score, word }));
+            }
+        }
+    }
+
+    let mut result: Vec<(&str, f32)> = heap.into_iter().map(|Reverse(s)| (s.word, s.score)).collect();
+    result.sort_by(|a, b| b.1.total_cmp(&a.1));
+    result
+}
+
+/// Reads and parses the `<count> <dim>\n` header line shared by the binary
+/// and plain-text formats, returning it along with the number of bytes
+/// consumed so callers can keep tracking absolute offsets for error reporting.
+fn parse_header_line<R: BufRead>(reader: &mut R) -> Result<(Word2VecHeader, usize), Error> {
+    let mut header_line = Vec::new();
+    let read = reader.read_until(b'\n', &mut header_line)?;
+    if read == 0 {
+        return Err(Error::UnexpectedEof);
+    }
+
+    let (_, header) = Word2VecHeader::parse(&header_line)?;
+    Ok((header, read))
+}
+
+/// Reads `header.embeddings_count` binary entries (word, space, `dim`
+/// little-endian `f32`s) from `reader`, starting at absolute byte `offset`.
+/// Each entry is buffered in full and handed to [`Word2VecEmbedding::parse`]
+/// so the actual entry layout is only implemented once, shared with the
+/// `mmap` path; a single reused buffer avoids a fresh allocation per entry.
+fn read_binary_entries<R: BufRead>(
+    mut reader: R,
+    header: &Word2VecHeader,
+    mut offset: usize,
+) -> Result<HashMap<String, Word2VecEmbedding>, Error> {
+    let float_block_len = header.embeddings_dim as usize * std::mem::size_of::<f32>();
+    let mut embeddings = HashMap::with_capacity(header.embeddings_count as usize);
+    let mut entry_buf = Vec::new();
+
+    for _ in 0..header.embeddings_count {
+        let entry_offset = offset;
+
+        entry_buf.clear();
+        let word_len = reader.read_until(b' ', &mut entry_buf)?;
+        if word_len == 0 || entry_buf.last() != Some(&b' ') {
+            return Err(Error::UnexpectedEof);
+        }
+        offset += word_len;
+
+        let value_start = entry_buf.len();
+        entry_buf.resize(value_start + float_block_len, 0);
+        reader.read_exact(&mut entry_buf[value_start..]).map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => Error::UnexpectedEof,
+            _ => Error::Io(e),
+        })?;
+        offset += float_block_len;
+
+        let (remaining, embedding) = Word2VecEmbedding::parse(&entry_buf, header.embeddings_dim, entry_offset)?;
+        assert!(remaining.is_empty()); // entry_buf holds exactly one entry's bytes
+
+        embeddings.insert(embedding.word.clone(), embedding);
+    }
+
+    Ok(embeddings)
+}
+
+/// Reads `header.embeddings_count` plain-text entries (one per line: a word
+/// token followed by `dim` whitespace-separated ASCII decimals) from
+/// `reader`, starting at absolute byte `offset`.
+fn read_text_entries<R: BufRead>(
+    mut reader: R,
+    header: &Word2VecHeader,
+    mut offset: usize,
+) -> Result<HashMap<String, Word2VecEmbedding>, Error> {
+    let mut embeddings = HashMap::with_capacity(header.embeddings_count as usize);
+
+    for _ in 0..header.embeddings_count {
+        let entry_offset = offset;
+
+        let mut line = String::new();
+        let read = reader.read_line(&mut line)?;
+        if read == 0 {
+            return Err(Error::UnexpectedEof);
+        }
+        offset += read;
+
+        let mut tokens = line.trim_end_matches(['\n', '\r']).split_whitespace();
+        let word = tokens.next().ok_or(Error::MissingWord { offset: entry_offset })?.to_string();
+
+        let embedding = tokens
+            .map(|t| t.parse::<f32>().map_err(|_| Error::InvalidFloat { offset: entry_offset }))
+            .collect::<Result<Vec<f32>, Error>>()?;
+
+        if embedding.len() != header.embeddings_dim as usize {
+            return Err(Error::DimensionMismatch {
+                expected: header.embeddings_dim as usize,
+                got: embedding.len(),
+            });
+        }
+
+        let norm = vector_norm(&embedding);
+        embeddings.insert(word.clone(), Word2VecEmbedding { word, embedding, norm });
+    }
+
+    Ok(embeddings)
+}
+
+/// Heuristic for autodetecting the plain-text format: a binary entry's raw
+/// little-endian floats are overwhelmingly non-printable bytes, while a text
+/// entry is plain ASCII.
+fn looks_like_text(bytes: &[u8]) -> bool {
+    let sample = &bytes[..bytes.len().min(64)];
+    !sample.is_empty() && sample.iter().all(|b| b.is_ascii_graphic() || b.is_ascii_whitespace())
+}
+
+pub struct Word2Vec {
     header: Word2VecHeader,
     embeddings: HashMap<String, Word2VecEmbedding>,
 }
 
 impl Word2Vec {
-    pub(crate) fn new(file: impl AsRef<Path>) -> Self {
-        let file = File::open(file).unwrap();
+    pub fn new(file: impl AsRef<Path>) -> Result<Self, Error> {
+        let file = File::open(file)?;
         // premature optimization
         // TODO: benchmark this vs. reading it normally
-        let mmap = unsafe { MmapOptions::new().map(&file).unwrap() };
-        let bytes = mmap.as_ref();
+        let mmap = unsafe { MmapOptions::new().map(&file)? };
+        let bytes: &[u8] = mmap.as_ref();
+        let total_len = bytes.len();
 
-        let (bytes, header) = Word2VecHeader::parse(bytes).expect("Cannot parse file header.");
+        let (mut remaining, header) = Word2VecHeader::parse(bytes)?;
 
-        let (bytes, embeddings_vec) = count(
-            |b| Word2VecEmbedding::parse(b, header.embeddings_dim),
-            header.embeddings_count as usize,
-        )(bytes)
-        .expect("Cannot parse embeddings.");
+        let mut embeddings = HashMap::with_capacity(header.embeddings_count as usize);
+        for _ in 0..header.embeddings_count {
+            let offset = total_len - remaining.len();
+            let (rest, embedding) = Word2VecEmbedding::parse(remaining, header.embeddings_dim, offset)?;
+            remaining = rest;
+            embeddings.insert(embedding.word.clone(), embedding);
+        }
 
-        assert_eq!(bytes.len(), 0); // we should be at the end of the file
+        if !remaining.is_empty() {
+            return Err(Error::TrailingBytes { remaining: remaining.len() });
+        }
 
-        // turn the embeddings into an HashMap
-        let embeddings = embeddings_vec
-            .into_iter()
-            .map(|e| (e.word.clone(), e))
-            .collect();
-
-        Word2Vec { header, embeddings }
+        Ok(Word2Vec { header, embeddings })
     }
 
     /// Get the dictionary
-    pub(crate) fn dictionary(&self) -> Vec<&str> {
+    pub fn dictionary(&self) -> Vec<&str> {
         self.embeddings.keys().map(AsRef::as_ref).collect()
     }
+
+    /// Get the embedding vector for `word`, or `None` if it isn't in the dictionary.
+    pub fn embedding(&self, word: &str) -> Option<&[f32]> {
+        self.embeddings.get(word).map(|e| e.embedding.as_slice())
+    }
+
+    /// Find the `k` words whose embeddings are most similar to `word`'s, ranked
+    /// by cosine similarity. `word` itself is excluded from the results.
+    /// Returns an empty vec if `word` isn't in the dictionary.
+    pub fn most_similar(&self, word: &str, k: usize) -> Vec<(&str, f32)> {
+        let Some(query) = self.embeddings.get(word) else {
+            return Vec::new();
+        };
+
+        // A zero vector has no defined direction, so cosine similarity against
+        // it (`0.0 / 0.0`) is NaN, not 0 — excluded on both sides so it never
+        // outranks a real candidate via total_cmp's NaN ordering.
+        if query.norm == 0.0 {
+            return Vec::new();
+        }
+
+        let candidates = self
+            .embeddings
+            .values()
+            .filter(|e| e.word != word && e.norm != 0.0)
+            .map(|e| {
+                let score = dot(&query.embedding, &e.embedding) / (query.norm * e.norm);
+                (e.word.as_str(), score)
+            });
+
+        top_k(candidates, k)
+    }
+
+    /// Classic word-analogy query: find the `k` words closest to
+    /// `sum(pos) - sum(neg)`, e.g. `analogy(&["king", "woman"], &["man"], 1)`
+    /// for `king - man + woman ≈ queen`. Words appearing in `pos` or `neg` are
+    /// excluded from the results. Returns an empty vec if any input word isn't
+    /// in the dictionary.
+    pub fn analogy(&self, pos: &[&str], neg: &[&str], k: usize) -> Vec<(&str, f32)> {
+        let dim = self.header.embeddings_dim as usize;
+        let mut query = vec![0f32; dim];
+
+        for word in pos {
+            let Some(e) = self.embeddings.get(*word) else {
+                return Vec::new();
+            };
+            for (q, v) in query.iter_mut().zip(&e.embedding) {
+                *q += v;
+            }
+        }
+
+        for word in neg {
+            let Some(e) = self.embeddings.get(*word) else {
+                return Vec::new();
+            };
+            for (q, v) in query.iter_mut().zip(&e.embedding) {
+                *q -= v;
+            }
+        }
+
+        let query_norm = vector_norm(&query);
+        if query_norm == 0.0 {
+            return Vec::new();
+        }
+
+        let excluded: HashSet<&str> = pos.iter().chain(neg.iter()).copied().collect();
+
+        let candidates = self
+            .embeddings
+            .values()
+            .filter(|e| !excluded.contains(e.word.as_str()) && e.norm != 0.0)
+            .map(|e| {
+                let score = dot(&query, &e.embedding) / (query_norm * e.norm);
+                (e.word.as_str(), score)
+            });
+
+        top_k(candidates, k)
+    }
+
+    /// Parses the binary word2vec format incrementally from any `BufRead`,
+    /// without requiring a file that can be memory-mapped (e.g. stdin, a
+    /// network stream, or a decompressor). The header is read as a line,
+    /// then each entry's word is read up to its separating space and its
+    /// float block with `read_exact`, so a source that can't yet supply a
+    /// full entry simply blocks or errors instead of being assumed to exist
+    /// in memory all at once. [`Word2Vec::new`] remains the fast path for
+    /// plain files, since it can avoid this copying via `mmap`.
+    pub fn from_reader<R: BufRead>(mut reader: R) -> Result<Self, Error> {
+        let (header, offset) = parse_header_line(&mut reader)?;
+        let embeddings = read_binary_entries(reader, &header, offset)?;
+        Ok(Word2Vec { header, embeddings })
+    }
+
+    /// Parses the plain-text word2vec/GloVe format: the same `<count>
+    /// <dim>` header as the binary format, followed by one line per word
+    /// where the dimensions are whitespace-separated ASCII decimals. This
+    /// covers the large body of GloVe-style vectors the binary format
+    /// can't express.
+    pub fn read_text<R: BufRead>(mut reader: R) -> Result<Self, Error> {
+        let (header, offset) = parse_header_line(&mut reader)?;
+        let embeddings = read_text_entries(reader, &header, offset)?;
+        Ok(Word2Vec { header, embeddings })
+    }
+
+    /// Parses either the binary or plain-text format from `reader`,
+    /// guessing which one it is by peeking at the bytes right after the
+    /// header: the binary format's raw little-endian floats are
+    /// overwhelmingly non-printable, while the text format is plain ASCII.
+    pub fn from_reader_auto<R: BufRead>(mut reader: R) -> Result<Self, Error> {
+        let (header, offset) = parse_header_line(&mut reader)?;
+
+        let is_text = looks_like_text(reader.fill_buf()?);
+
+        let embeddings = if is_text {
+            read_text_entries(reader, &header, offset)?
+        } else {
+            read_binary_entries(reader, &header, offset)?
+        };
+
+        Ok(Word2Vec { header, embeddings })
+    }
+
+    /// Opens `path` and transparently decompresses it if it's gzip- or
+    /// zlib-compressed (sniffed from its leading magic bytes), before
+    /// parsing it as the binary word2vec format. Most pretrained vectors
+    /// are distributed as `.bin.gz`, and this lets callers point straight
+    /// at the downloaded file without a manual decompression step.
+    /// Compressed input can't be memory-mapped, so this builds on
+    /// [`Word2Vec::from_reader`] rather than the `mmap` fast path.
+    pub fn from_path_auto(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let file = File::open(path)?;
+        let mut reader = BufReader::new(file);
+
+        let magic = reader.fill_buf()?;
+        let is_gzip = magic.starts_with(&[0x1f, 0x8b]);
+        let is_zlib = magic.first() == Some(&0x78);
+
+        if is_gzip {
+            Word2Vec::from_reader(BufReader::new(GzDecoder::new(reader)))
+        } else if is_zlib {
+            Word2Vec::from_reader(BufReader::new(ZlibDecoder::new(reader)))
+        } else {
+            Word2Vec::from_reader(reader)
+        }
+    }
+
+    /// Write this model out in the binary word2vec format: the
+    /// `<count><SP><dim><LF>` header followed by, for each entry, the word,
+    /// a space, and `dim` little-endian `f32` values. This is the exact
+    /// layout [`Word2Vec::new`] expects to read back.
+    pub fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let dim = self.header.embeddings_dim as usize;
+
+        // self.header.embeddings_count is whatever the source file claimed up
+        // front, but duplicate words collapse in the HashMap during parsing,
+        // so the header must reflect what we're actually about to write.
+        writeln!(w, "{} {}", self.embeddings.len(), self.header.embeddings_dim)?;
+
+        for embedding in self.embeddings.values() {
+            if embedding.embedding.len() != dim {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "embedding {:?} has {} dimension(s), expected {dim}",
+                        embedding.word,
+                        embedding.embedding.len()
+                    ),
+                ));
+            }
+
+            write!(w, "{} ", embedding.word)?;
+
+            for value in &embedding.embedding {
+                w.write_f32::<LittleEndian>(*value)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Convenience wrapper around [`Word2Vec::write`] that creates (or
+    /// truncates) the file at `path` and writes this model to it.
+    pub fn write_to_path(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let file = File::create(path)?;
+        let mut writer = BufWriter::new(file);
+        self.write(&mut writer)?;
+        writer.flush()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn make_word2vec(entries: &[(&str, Vec<f32>)]) -> Word2Vec {
+        let dim = entries.first().map_or(0, |(_, v)| v.len()) as u32;
+
+        let embeddings = entries
+            .iter()
+            .map(|(word, embedding)| {
+                let norm = vector_norm(embedding);
+                let word = word.to_string();
+                (
+                    word.clone(),
+                    Word2VecEmbedding { word, embedding: embedding.clone(), norm },
+                )
+            })
+            .collect();
+
+        Word2Vec {
+            header: Word2VecHeader {
+                embeddings_count: entries.len() as u32,
+                embeddings_dim: dim,
+            },
+            embeddings,
+        }
+    }
+
+    #[test]
+    fn test_most_similar_ranks_by_cosine_similarity() {
+        let w2v = make_word2vec(&[("a", vec![1.0, 0.0]), ("b", vec![0.0, 1.0]), ("c", vec![1.0, 0.0])]);
+
+        let scores: HashMap<&str, f32> = w2v.most_similar("a", 2).into_iter().collect();
+
+        assert!((scores[&"c"] - 1.0).abs() < 1e-6); // identical vector
+        assert!(scores[&"b"].abs() < 1e-6); // orthogonal vector
+    }
+
+    #[test]
+    fn test_most_similar_skips_zero_norm_embeddings() {
+        let w2v = make_word2vec(&[("a", vec![1.0, 0.0]), ("unk", vec![0.0, 0.0])]);
+
+        let results = w2v.most_similar("a", 5);
+
+        assert!(results.iter().all(|(word, _)| *word != "unk"));
+    }
+
+    #[test]
+    fn test_most_similar_on_zero_norm_query_is_empty() {
+        let w2v = make_word2vec(&[("unk", vec![0.0, 0.0]), ("a", vec![1.0, 0.0])]);
+
+        assert!(w2v.most_similar("unk", 5).is_empty());
+    }
+
+    #[test]
+    fn test_analogy_king_man_woman() {
+        let w2v = make_word2vec(&[
+            ("king", vec![1.0, 1.0]),
+            ("man", vec![1.0, 0.0]),
+            ("woman", vec![0.0, 1.0]),
+            ("queen", vec![0.0, 2.0]),
+        ]);
+
+        let results = w2v.analogy(&["king", "woman"], &["man"], 1);
+
+        assert_eq!(results[0].0, "queen");
+    }
+
+    #[test]
+    fn test_analogy_skips_zero_norm_embeddings() {
+        let w2v = make_word2vec(&[
+            ("king", vec![1.0, 1.0]),
+            ("man", vec![1.0, 0.0]),
+            ("woman", vec![0.0, 1.0]),
+            ("unk", vec![0.0, 0.0]),
+        ]);
+
+        let results = w2v.analogy(&["king", "woman"], &["man"], 5);
+
+        assert!(results.iter().all(|(word, _)| *word != "unk"));
+    }
+
+    #[test]
+    fn test_write_then_from_reader_round_trips() {
+        let w2v = make_word2vec(&[("a", vec![1.0, 2.0]), ("b", vec![-3.5, 0.25])]);
+
+        let mut buf = Vec::new();
+        w2v.write(&mut buf).unwrap();
+
+        let round_tripped = Word2Vec::from_reader(buf.as_slice()).unwrap();
+
+        let mut dictionary = round_tripped.dictionary();
+        dictionary.sort();
+        assert_eq!(dictionary, vec!["a", "b"]);
+
+        assert_eq!(round_tripped.embedding("a"), Some([1.0, 2.0].as_slice()));
+        assert_eq!(round_tripped.embedding("b"), Some([-3.5, 0.25].as_slice()));
+    }
+
+    #[test]
+    fn test_write_uses_actual_entry_count_not_stale_header_count() {
+        let mut w2v = make_word2vec(&[("a", vec![1.0, 2.0])]);
+        // Mirrors what parsing a file with duplicate words produces: the
+        // HashMap collapses them, so the header's original count no longer
+        // matches the number of entries actually stored.
+        w2v.header.embeddings_count = 2;
+
+        let mut buf = Vec::new();
+        w2v.write(&mut buf).unwrap();
+
+        let round_tripped = Word2Vec::from_reader(buf.as_slice()).unwrap();
+        assert_eq!(round_tripped.dictionary(), vec!["a"]);
+    }
+
+    #[test]
+    fn test_from_reader_parses_valid_buffer() {
+        let w2v = make_word2vec(&[("a", vec![1.0, 2.0]), ("b", vec![-3.5, 0.25])]);
+
+        let mut buf = Vec::new();
+        w2v.write(&mut buf).unwrap();
+
+        let parsed = Word2Vec::from_reader(buf.as_slice()).unwrap();
+
+        assert_eq!(parsed.embedding("a"), Some([1.0, 2.0].as_slice()));
+        assert_eq!(parsed.embedding("b"), Some([-3.5, 0.25].as_slice()));
+    }
+
+    #[test]
+    fn test_from_reader_truncated_float_block_is_unexpected_eof() {
+        let w2v = make_word2vec(&[("a", vec![1.0, 2.0])]);
+
+        let mut buf = Vec::new();
+        w2v.write(&mut buf).unwrap();
+        buf.truncate(buf.len() - 1); // drop the last byte of the float block
+
+        assert!(matches!(Word2Vec::from_reader(buf.as_slice()), Err(Error::UnexpectedEof)));
+    }
+
+    #[test]
+    fn test_from_reader_invalid_utf8_word_is_reported() {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(b"1 1\n");
+        buf.push(0xff); // not valid UTF-8
+        buf.push(b' ');
+        buf.extend_from_slice(&1.0f32.to_le_bytes());
+
+        assert!(matches!(Word2Vec::from_reader(buf.as_slice()), Err(Error::InvalidUtf8Word { .. })));
+    }
+
+    #[test]
+    fn test_read_text_parses_valid_buffer() {
+        let text = b"2 2\na 1.0 2.0\nb -3.5 0.25\n";
+
+        let w2v = Word2Vec::read_text(text.as_slice()).unwrap();
+
+        assert_eq!(w2v.embedding("a"), Some([1.0, 2.0].as_slice()));
+        assert_eq!(w2v.embedding("b"), Some([-3.5, 0.25].as_slice()));
+    }
+
+    #[test]
+    fn test_read_text_strips_trailing_carriage_return() {
+        let text = b"1 2\na 1.0 2.0\r\n";
+
+        let w2v = Word2Vec::read_text(text.as_slice()).unwrap();
+
+        assert_eq!(w2v.embedding("a"), Some([1.0, 2.0].as_slice()));
+    }
+
+    #[test]
+    fn test_read_text_blank_line_is_missing_word() {
+        let text = b"1 2\n\n";
+
+        assert!(matches!(Word2Vec::read_text(text.as_slice()), Err(Error::MissingWord { .. })));
+    }
+
+    #[test]
+    fn test_read_text_wrong_dimension_count_is_dimension_mismatch() {
+        let text = b"1 2\na 1.0\n";
+
+        assert!(matches!(
+            Word2Vec::read_text(text.as_slice()),
+            Err(Error::DimensionMismatch { expected: 2, got: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_from_reader_auto_detects_text_format() {
+        let text = b"1 2\na 1.0 2.0\n";
+
+        let w2v = Word2Vec::from_reader_auto(text.as_slice()).unwrap();
+
+        assert_eq!(w2v.embedding("a"), Some([1.0, 2.0].as_slice()));
+    }
+
+    #[test]
+    fn test_from_reader_auto_detects_binary_format() {
+        let w2v = make_word2vec(&[("a", vec![1.0, 2.0])]);
+        let mut buf = Vec::new();
+        w2v.write(&mut buf).unwrap();
+
+        let parsed = Word2Vec::from_reader_auto(buf.as_slice()).unwrap();
+
+        assert_eq!(parsed.embedding("a"), Some([1.0, 2.0].as_slice()));
+    }
+
+    #[test]
+    fn test_looks_like_text() {
+        assert!(looks_like_text(b"hello 1.0 2.0\n"));
+        assert!(!looks_like_text(&1.0f32.to_le_bytes()));
+        assert!(!looks_like_text(b""));
+    }
+
     #[test]
     fn test_ascii_u32_terminated_by_ok() {
         let n = 923732897_u32;
@@ -161,4 +743,78 @@ mod tests {
 
         ascii_u32_terminated_by(s.as_bytes(), delimiter as u8).unwrap();
     }
+
+    #[test]
+    fn test_ascii_u32_terminated_by_overflow_does_not_panic() {
+        let s = "99999999999999999999999;";
+
+        assert!(ascii_u32_terminated_by(s.as_bytes(), b';').is_err());
+    }
+
+    #[test]
+    fn test_header_parse_overflowing_count_is_bad_header() {
+        let bytes = b"99999999999999999999999 300\n";
+
+        assert!(matches!(Word2VecHeader::parse(bytes), Err(Error::BadHeader)));
+    }
+
+    fn write_temp_file(name: &str, bytes: &[u8]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("fckng_w2v_test_{}_{name}", std::process::id()));
+        std::fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_from_path_auto_reads_gzip_compressed_file() {
+        use flate2::{write::GzEncoder, Compression};
+
+        let w2v = make_word2vec(&[("a", vec![1.0, 2.0])]);
+        let mut plain = Vec::new();
+        w2v.write(&mut plain).unwrap();
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&plain).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let path = write_temp_file("gzip.bin.gz", &compressed);
+        let loaded = Word2Vec::from_path_auto(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.dictionary(), vec!["a"]);
+        assert_eq!(loaded.embedding("a"), Some(&[1.0, 2.0][..]));
+    }
+
+    #[test]
+    fn test_from_path_auto_reads_zlib_compressed_file() {
+        use flate2::{write::ZlibEncoder, Compression};
+
+        let w2v = make_word2vec(&[("a", vec![1.0, 2.0])]);
+        let mut plain = Vec::new();
+        w2v.write(&mut plain).unwrap();
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&plain).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let path = write_temp_file("zlib.bin.z", &compressed);
+        let loaded = Word2Vec::from_path_auto(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.dictionary(), vec!["a"]);
+        assert_eq!(loaded.embedding("a"), Some(&[1.0, 2.0][..]));
+    }
+
+    #[test]
+    fn test_from_path_auto_falls_back_to_uncompressed() {
+        let w2v = make_word2vec(&[("a", vec![1.0, 2.0])]);
+        let mut plain = Vec::new();
+        w2v.write(&mut plain).unwrap();
+
+        let path = write_temp_file("plain.bin", &plain);
+        let loaded = Word2Vec::from_path_auto(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.dictionary(), vec!["a"]);
+        assert_eq!(loaded.embedding("a"), Some(&[1.0, 2.0][..]));
+    }
 }